@@ -0,0 +1,114 @@
+//! Direct hardware-device output: instead of (or alongside) feeding the
+//! channel's bus to a `Return` instance, stream it straight to a physical
+//! output device via `cpal`, so `EasySend` can also act as a router — e.g.
+//! DAW audio to a headphone interface independent of the host's master bus.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+
+/// `device_name` empty means "the host's default output device". Matching
+/// is by exact name — if it's since disappeared we fall back to default
+/// rather than erroring, same spirit as `clamp_delay`'s graceful degradation.
+fn resolve_device(host: &cpal::Host, device_name: &str) -> Option<cpal::Device> {
+    if device_name.is_empty() {
+        return host.default_output_device();
+    }
+    host.output_devices()
+        .ok()?
+        .find(|d| d.name().map(|n| n == device_name).unwrap_or(false))
+        .or_else(|| host.default_output_device())
+}
+
+/// Runs on a background task: opens the device, then drains `ring` into it
+/// until `stop` is set. Never touches the plugin's own audio thread.
+pub fn run_output(
+    channel_idx: usize,
+    num_physical: usize,
+    device_name: String,
+    requested_period: u32,
+    host_sample_rate: f32,
+    stop: Arc<AtomicBool>,
+) {
+    let host = cpal::default_host();
+    let Some(device) = resolve_device(&host, &device_name) else { return };
+    let Ok(supported) = device.default_output_config() else { return };
+
+    let device_channels = supported.channels() as usize;
+    let device_sample_rate = supported.sample_rate().0 as f32;
+    let sample_format = supported.sample_format();
+
+    let mut config: cpal::StreamConfig = supported.into();
+    config.buffer_size = cpal::BufferSize::Fixed(requested_period);
+
+    let ring = crate::channel_ring_for(channel_idx, num_physical);
+    let resample_ratio = host_sample_rate / device_sample_rate;
+    // Jitter buffer: stay this far behind the writer, same idea as cpal's own
+    // backends keeping ~2x the period of internal buffering. `requested_period`
+    // is a device-rate frame count (it's the cpal buffer period), but write_pos
+    // and read_cursor are both host-rate, so convert before using it as an
+    // offset into the ring — otherwise this holds back the wrong amount of
+    // real time whenever the device and host sample rates differ.
+    let jitter_frames = (requested_period as f64 * 2.0 * resample_ratio).round() as usize;
+    let mut read_cursor = 0.0f64; // fractional position, in host-rate frames
+
+    let mut primed = false;
+
+    let mut write_frame = move |out: &mut [f32]| {
+        let write_pos = ring.write_pos.load(Ordering::Acquire);
+
+        if !primed {
+            if write_pos < jitter_frames {
+                out.fill(0.0);
+                return;
+            }
+            read_cursor = (write_pos - jitter_frames) as f64;
+            primed = true;
+        }
+
+        for frame in out.chunks_mut(device_channels) {
+            let base = read_cursor.floor() as usize;
+            let frac = read_cursor.fract() as f32;
+
+            if base + 1 >= write_pos {
+                // Ran out of headroom (writer stalled) — hold silence rather
+                // than reading ahead of what's been written.
+                frame.fill(0.0);
+            } else {
+                for (dev_ch, sample) in frame.iter_mut().enumerate() {
+                    // Map device channels onto the ring's physical channels;
+                    // wrap around if the device has more channels than we do.
+                    let src_ch = dev_ch % num_physical;
+                    let a = ring.rings[src_ch].load_at(base);
+                    let b = ring.rings[src_ch].load_at(base + 1);
+                    *sample = a + (b - a) * frac;
+                }
+            }
+
+            read_cursor += resample_ratio as f64;
+        }
+    };
+
+    let err_fn = |_err: cpal::StreamError| {};
+
+    let stream = match sample_format {
+        cpal::SampleFormat::F32 => device.build_output_stream(
+            &config,
+            move |data: &mut [f32], _| write_frame(data),
+            err_fn,
+            None,
+        ),
+        _ => return, // only f32 device streams are supported for now
+    };
+
+    let Ok(stream) = stream else { return };
+    if stream.play().is_err() {
+        return;
+    }
+
+    while !stop.load(Ordering::Acquire) {
+        std::thread::sleep(std::time::Duration::from_millis(20));
+    }
+    // `stream` is dropped here, which stops and tears it down.
+}
@@ -1,63 +1,199 @@
 use nih_plug::prelude::*;
-use parking_lot::Mutex;
+use arc_swap::ArcSwap;
 use std::sync::Arc;
 use std::num::NonZeroU32;
-use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{OnceLock, RwLock};
 use std::collections::HashMap;
 
+mod meter;
+use meter::{LoudnessMeter, LoudnessReadout};
+
+mod network;
+use network::OutboundQueue;
+
+mod hardware;
+
 const MAX_CHANNELS: usize = 64;
+/// Upper bound on a ring's physical channel count. The plugin itself is
+/// stereo-only (see `AUDIO_IO_LAYOUTS`), but network peers report their own
+/// `num_physical` on the wire; this caps how much a single (malformed or
+/// hostile) packet can make `channel_ring_for` allocate — each physical
+/// channel costs one `Ring` of `RING_CAP_POW2` `AtomicU64`s.
+const MAX_PHYSICAL_CHANNELS: usize = 8;
 // capacity must be power of two
 const RING_CAP_POW2: usize = 1 << 16;
-const DESIRED_DELAY_SAMPLES: usize = 16;
+const DEFAULT_DELAY_SAMPLES: i32 = 16;
+
+/// A `Return`'s delay must leave at least one full block of headroom in the
+/// ring (otherwise the writer could lap the reader within a single block),
+/// so the usable range tops out at `RING_CAP_POW2 - frame_count`. Clamp
+/// rather than refuse, so a too-large request degrades to the largest safe
+/// delay instead of silence.
+fn clamp_delay(requested: u32, frame_count: usize) -> usize {
+    let headroom_cap = RING_CAP_POW2.saturating_sub(frame_count.max(1));
+    (requested as usize).min(headroom_cap)
+}
+
+/// Only `Mode::Return` actually delays audio (by trailing `write_pos` by
+/// `active_delay`); a `Send` passes audio through with no added latency of
+/// its own, so it must report zero rather than whatever delay is dialed in.
+fn reported_latency(mode: Mode, active_delay: usize) -> u32 {
+    match mode {
+        Mode::Return => active_delay as u32,
+        Mode::Send => 0,
+    }
+}
 
 struct Ring {
-    buf: Vec<AtomicU32>, // store f32 as bits in AtomicU32
+    // Each slot packs `(generation: u32) << 32 | (sample bits: u32)` into one
+    // word so a slot's lap number and its value move in a single CAS — see
+    // `add_at`.
+    slots: Vec<AtomicU64>,
+}
+
+#[inline]
+fn pack_slot(generation: u32, bits: u32) -> u64 {
+    ((generation as u64) << 32) | bits as u64
+}
+
+#[inline]
+fn unpack_slot(word: u64) -> (u32, u32) {
+    ((word >> 32) as u32, word as u32)
 }
 
 impl Ring {
     fn new(capacity_pow2: usize) -> Self {
-        let mut v = Vec::with_capacity(capacity_pow2);
+        let mut slots = Vec::with_capacity(capacity_pow2);
         for _ in 0..capacity_pow2 {
-            v.push(AtomicU32::new(0));
+            // generation 0 belongs to the very first lap, so seed with a
+            // generation that can never match a real one until claimed.
+            slots.push(AtomicU64::new(pack_slot(u32::MAX, 0)));
         }
-        Self { buf: v }
+        Self { slots }
     }
 
     #[inline]
-    fn store_at(&self, idx: usize, sample: f32) {
-        let bits = sample.to_bits();
-        let i = idx & (self.buf.len() - 1);
-        self.buf[i].store(bits, Ordering::Release);
+    fn load_at(&self, idx: usize) -> f32 {
+        let i = idx & (self.slots.len() - 1);
+        let (_, bits) = unpack_slot(self.slots[i].load(Ordering::Acquire));
+        f32::from_bits(bits)
     }
 
+    /// Additive write for summing aux buses: the first sender to touch frame
+    /// `idx` in a given lap around the ring overwrites (starts the sum), every
+    /// later sender in the same lap adds on top of it. `idx`'s lap number
+    /// (`idx / slots.len()`) is the generation, so slots self-rotate as
+    /// `write_pos` sweeps past them again — no separate cycle counter needed,
+    /// and nothing for the reader to reset.
+    ///
+    /// The generation and the value live in one `AtomicU64`, so "is this the
+    /// first write of the lap" and "store/add the value" happen as a single
+    /// compare-exchange: a writer can't observe another writer's claim before
+    /// that writer's value has landed, which is what made the old two-atomic
+    /// (epoch + buf) version lose contributions under concurrency.
     #[inline]
-    fn load_at(&self, idx: usize) -> f32 {
-        let i = idx & (self.buf.len() - 1);
-        let bits = self.buf[i].load(Ordering::Acquire);
-        f32::from_bits(bits)
+    fn add_at(&self, idx: usize, sample: f32) {
+        let cap = self.slots.len();
+        let i = idx & (cap - 1);
+        let generation = (idx / cap) as u32;
+        let slot = &self.slots[i];
+
+        let mut current = slot.load(Ordering::Acquire);
+        loop {
+            let (cur_generation, cur_bits) = unpack_slot(current);
+            let new_value = if cur_generation == generation {
+                f32::from_bits(cur_bits) + sample
+            } else {
+                // First write of this lap: start the sum fresh.
+                sample
+            };
+            let new_word = pack_slot(generation, new_value.to_bits());
+            match slot.compare_exchange_weak(current, new_word, Ordering::AcqRel, Ordering::Acquire) {
+                Ok(_) => return,
+                Err(actual) => current = actual, // retry, re-deciding against the fresh word
+            }
+        }
     }
 }
 
+/// A summing aux bus: every `Send` instance targeting this `(channel,
+/// num_physical)` key is a contributor, matching how a real DAW aux send
+/// mixes any number of source tracks into one bus for the `Return` to hear.
 struct ChannelRing {
-    /// write_pos counts written FRAMES (not samples across all physical channels).
-    /// In other words, if we've written N frames (each frame = one sample per physical channel),
-    /// write_pos == N.
+    /// write_pos counts FULLY WRITTEN frames (not samples across all physical
+    /// channels): every contributor's `add_at` for frame `i < write_pos` has
+    /// already landed, so it's safe for a reader to consume up to here. In
+    /// other words, if we've written N frames (each frame = one sample per
+    /// physical channel), write_pos == N.
     write_pos: AtomicUsize,
+    /// Private counter for claiming a frame range when no host transport
+    /// position is available (see `Mode::Send`'s fallback in `process`).
+    /// Unlike `write_pos`, bumping this says nothing about what's actually
+    /// been written yet — it only reserves a base so concurrent lone-sender
+    /// blocks don't pick the same range.
+    claim_pos: AtomicUsize,
     rings: Vec<Ring>, // rings.len() == num_physical_channels
 }
 
-// Use a more flexible storage that can handle different channel counts per instance
-static mut GLOBAL_CHANNEL_RINGS: Option<Arc<Mutex<HashMap<(usize, usize), Arc<ChannelRing>>>>> = None;
+// Registry of every (channel, num_physical) bus that's been touched so far.
+// Reads never block a writer and never block each other: `ArcSwap::load`
+// just atomically loads the current immutable `Arc<HashMap>`. Publishing a
+// new entry clones the map and CAS-swaps it in, but that only happens the
+// first time a given (channel, num_physical) combination is seen — every
+// instance caches its own `Arc<ChannelRing>` afterwards (see `EasySend::
+// cached_ring`) so the steady-state hot path never touches this at all.
+static GLOBAL_CHANNEL_RINGS: OnceLock<ArcSwap<HashMap<(usize, usize), Arc<ChannelRing>>>> =
+    OnceLock::new();
+
+fn global_channel_rings() -> &'static ArcSwap<HashMap<(usize, usize), Arc<ChannelRing>>> {
+    GLOBAL_CHANNEL_RINGS.get_or_init(|| ArcSwap::from_pointee(HashMap::new()))
+}
+
+/// Non-allocating registry lookup: returns the ring only if some other
+/// caller has already created it. Safe to call from the audio thread, unlike
+/// `channel_ring_for`, which allocates on a miss — see `process`'s
+/// `channel_changed` handling.
+fn peek_channel_ring(channel_idx: usize, num_physical: usize) -> Option<Arc<ChannelRing>> {
+    global_channel_rings().load().get(&(channel_idx, num_physical)).cloned()
+}
+
+/// Get (or lazily create) the summing bus for `(channel_idx, num_physical)`.
+/// Shared by `initialize` (off the audio thread) and by the network receiver
+/// thread writing mirrored frames into the same bus. Allocates on a miss, so
+/// `process` must never call this directly — see `peek_channel_ring` and
+/// `PluginTask::WarmRing`.
+fn channel_ring_for(channel_idx: usize, num_physical: usize) -> Arc<ChannelRing> {
+    let key = (channel_idx, num_physical);
+    let registry = global_channel_rings();
+
+    if let Some(existing) = registry.load().get(&key) {
+        return existing.clone();
+    }
+
+    let mut vec_rings = Vec::with_capacity(num_physical);
+    for _ in 0..num_physical {
+        vec_rings.push(Ring::new(RING_CAP_POW2));
+    }
+    let cr = Arc::new(ChannelRing {
+        write_pos: AtomicUsize::new(0),
+        claim_pos: AtomicUsize::new(0),
+        rings: vec_rings,
+    });
+
+    loop {
+        let current = registry.load();
+        if let Some(existing) = current.get(&key) {
+            return existing.clone();
+        }
 
-fn global_channel_rings() -> Arc<Mutex<HashMap<(usize, usize), Arc<ChannelRing>>>> {
-    unsafe {
-        if let Some(ref v) = GLOBAL_CHANNEL_RINGS {
-            return v.clone();
+        let mut next = HashMap::clone(&current);
+        next.insert(key, cr.clone());
+        let prev = registry.compare_and_swap(&current, Arc::new(next));
+        if Arc::ptr_eq(&prev, &current) {
+            return cr;
         }
-        let map = HashMap::new();
-        let arc = Arc::new(Mutex::new(map));
-        GLOBAL_CHANNEL_RINGS = Some(arc.clone());
-        arc
+        // Someone else published a registry update concurrently; retry.
     }
 }
 
@@ -74,6 +210,36 @@ struct EasySendParams {
 
     #[id = "output"]
     pub output: EnumParam<OutputMode>,
+
+    #[id = "net_encrypt"]
+    pub network_encrypt: BoolParam,
+
+    /// `host:port` of the remote peer: the address a `Send` connects out to,
+    /// or the address a `Return` binds and listens on. Not automatable, so
+    /// it's a persisted field rather than an `id`-tagged param.
+    #[persist = "net-addr"]
+    pub network_addr: RwLock<String>,
+
+    /// Shared passphrase for the keystream cipher; empty means unencrypted.
+    #[persist = "net-key"]
+    pub network_key: RwLock<String>,
+
+    /// How many samples a `Return` holds audio back by before handing it to
+    /// the host, reported to the host as plugin latency so PDC keeps it
+    /// sample-accurate with the dry path. Range is derived from the ring
+    /// capacity; see `clamp_delay`.
+    #[id = "delay"]
+    pub delay_samples: IntParam,
+
+    /// Name of the physical output device for `OutputMode::HardwareOutput`;
+    /// empty means "the host's default device". Not automatable, like the
+    /// network address.
+    #[persist = "hw-device"]
+    pub hardware_device: RwLock<String>,
+
+    /// Requested device buffer/period size in frames.
+    #[id = "hw_period"]
+    pub hardware_period: IntParam,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Enum)]
@@ -86,6 +252,45 @@ enum Mode {
 enum OutputMode {
     PassThrough,
     Redirect,
+    /// Mirror this channel's bus to another machine/process over TCP instead
+    /// of (or in addition to) the local ring: a `Send` dials out, a `Return`
+    /// listens and feeds arriving frames into the local `ChannelRing`.
+    Network,
+    /// Stream this channel's bus straight to a physical output device via
+    /// `cpal`, independent of the host's master bus (e.g. DAW → headphones).
+    HardwareOutput,
+}
+
+/// Work handed to the host's background executor so neither a TCP connect
+/// nor a blocking accept ever happens on the audio thread.
+#[derive(Clone)]
+enum PluginTask {
+    StartSender {
+        queue: Arc<OutboundQueue>,
+        addr: String,
+        key: Option<String>,
+    },
+    StartReceiver {
+        stop: Arc<AtomicBool>,
+        addr: String,
+        key: Option<String>,
+    },
+    StartHardwareOutput {
+        channel_idx: usize,
+        num_physical: usize,
+        device_name: String,
+        period: u32,
+        sample_rate: f32,
+        stop: Arc<AtomicBool>,
+    },
+    /// Create (and register) the `(channel_idx, num_physical)` ring that
+    /// `process` found missing, so the allocation happens off the audio
+    /// thread. `process` picks the result up via `peek_channel_ring` on a
+    /// later block — see its `channel_changed` handling.
+    WarmRing {
+        channel_idx: usize,
+        num_physical: usize,
+    },
 }
 
 struct EasySend {
@@ -95,6 +300,31 @@ struct EasySend {
     read_initialized: bool,
     last_channel: usize,
     last_num_channels: usize,
+    // Only Mode::Return actually delays audio; tracked so we can re-report
+    // latency to the host when an automated mode switch flips which is true.
+    last_mode: Mode,
+    // Resolved once per (channel, num_physical) change and reused for every
+    // block after that, so the realtime hot path never touches the registry.
+    cached_ring: Option<Arc<ChannelRing>>,
+    // Set while a PluginTask::WarmRing for the current (channel, num_physical)
+    // is in flight, so process() doesn't re-dispatch it every block while
+    // waiting for the background thread to create and register the ring.
+    ring_warming: bool,
+    // loudness/true-peak metering of whatever passes through this instance
+    meter: LoudnessMeter,
+    readout: Arc<LoudnessReadout>,
+    sample_rate: f32,
+    frame_scratch: Vec<f32>,
+    // network mirror state (only live while output == Network)
+    network_started: bool,
+    outbound_queue: Option<Arc<OutboundQueue>>,
+    network_stop: Option<Arc<AtomicBool>>,
+    // the delay actually in effect (post-clamp), so we know when to re-derive
+    // read_pos and when to tell the host latency changed
+    active_delay: usize,
+    // hardware output mirror state (only live while output == HardwareOutput)
+    hardware_started: bool,
+    hardware_stop: Option<Arc<AtomicBool>>,
 }
 
 impl Default for EasySend {
@@ -105,15 +335,69 @@ impl Default for EasySend {
                 mode: EnumParam::new("Mode", Mode::Send),
                 amount: FloatParam::new("Amount", 1.0, FloatRange::Linear { min: 0.0, max: 1.0 }),
                 output: EnumParam::new("Output", OutputMode::PassThrough),
+                network_encrypt: BoolParam::new("Network Encrypt", false),
+                network_addr: RwLock::new("127.0.0.1:9000".to_string()),
+                network_key: RwLock::new(String::new()),
+                delay_samples: IntParam::new(
+                    "Return Delay",
+                    DEFAULT_DELAY_SAMPLES,
+                    IntRange::Linear { min: 0, max: (RING_CAP_POW2 / 2) as i32 },
+                )
+                .with_unit(" samples"),
+                hardware_device: RwLock::new(String::new()),
+                hardware_period: IntParam::new(
+                    "Hardware Period",
+                    512,
+                    IntRange::Linear { min: 64, max: 4096 },
+                )
+                .with_unit(" frames"),
             }),
             read_pos: 0,
             read_initialized: false,
             last_channel: 0,
             last_num_channels: 0,
+            last_mode: Mode::Send,
+            cached_ring: None,
+            ring_warming: false,
+            meter: LoudnessMeter::new(44_100.0, 2),
+            readout: Arc::new(LoudnessReadout::default()),
+            sample_rate: 44_100.0,
+            frame_scratch: Vec::new(),
+            network_started: false,
+            outbound_queue: None,
+            network_stop: None,
+            active_delay: DEFAULT_DELAY_SAMPLES as usize,
+            hardware_started: false,
+            hardware_stop: None,
         }
     }
 }
 
+impl EasySend {
+    /// A clone of the atomic loudness/true-peak readout, for an editor (or
+    /// anything else polling from off the audio thread) to visualize.
+    pub fn loudness(&self) -> Arc<LoudnessReadout> {
+        self.readout.clone()
+    }
+
+    fn stop_network(&mut self) {
+        if let Some(queue) = self.outbound_queue.take() {
+            queue.stop();
+        }
+        if let Some(stop) = self.network_stop.take() {
+            stop.store(true, Ordering::Release);
+        }
+        self.network_started = false;
+    }
+
+    fn stop_hardware(&mut self) {
+        if let Some(stop) = self.hardware_stop.take() {
+            stop.store(true, Ordering::Release);
+        }
+        self.hardware_started = false;
+    }
+}
+
 impl Plugin for EasySend {
     const NAME: &'static str = "Easy Send";
     const VENDOR: &'static str = "Lath Audio";
@@ -135,7 +419,40 @@ impl Plugin for EasySend {
     const SAMPLE_ACCURATE_AUTOMATION: bool = true;
 
     type SysExMessage = ();
-    type BackgroundTask = ();
+    type BackgroundTask = PluginTask;
+
+    fn task_executor(&mut self) -> TaskExecutor<Self> {
+        // Socket setup and the blocking accept/connect loop belong off the
+        // audio thread; the executor just spawns a plain thread per task.
+        Box::new(|task| match task {
+            PluginTask::StartSender { queue, addr, key } => {
+                std::thread::spawn(move || network::run_sender(queue, addr, key));
+            }
+            PluginTask::StartReceiver { stop, addr, key } => {
+                std::thread::spawn(move || network::run_receiver(addr, key, stop));
+            }
+            PluginTask::StartHardwareOutput {
+                channel_idx,
+                num_physical,
+                device_name,
+                period,
+                sample_rate,
+                stop,
+            } => {
+                std::thread::spawn(move || {
+                    hardware::run_output(channel_idx, num_physical, device_name, period, sample_rate, stop)
+                });
+            }
+            PluginTask::WarmRing { channel_idx, num_physical } => {
+                // channel_ring_for is idempotent (checks the registry first),
+                // so it's harmless if more than one instance races to warm
+                // the same key.
+                std::thread::spawn(move || {
+                    channel_ring_for(channel_idx, num_physical);
+                });
+            }
+        })
+    }
 
     fn params(&self) -> Arc<dyn Params> {
         self.params.clone()
@@ -143,13 +460,45 @@ impl Plugin for EasySend {
 
     fn initialize(
         &mut self,
-        _audio_io_layout: &AudioIOLayout,
-        _buffer_config: &BufferConfig,
-        _context: &mut impl InitContext<Self>,
+        audio_io_layout: &AudioIOLayout,
+        buffer_config: &BufferConfig,
+        context: &mut impl InitContext<Self>,
     ) -> bool {
         self.read_initialized = false;
-        self.last_channel = 0;
-        self.last_num_channels = 0;
+
+        let num_channels = audio_io_layout
+            .main_input_channels
+            .map(|n| n.get() as usize)
+            .unwrap_or(2);
+
+        // Resolve (and allocate, if this is the first instance on this
+        // channel) the bus here, off the audio thread, so `process` never
+        // has to allocate it itself; at most its first block (after `reset`
+        // clears `last_channel`) re-finds it with a non-allocating
+        // `peek_channel_ring`.
+        let channel_idx = (self.params.channel.value() as usize).min(MAX_CHANNELS - 1);
+        self.cached_ring = Some(channel_ring_for(channel_idx, num_channels));
+        self.last_channel = channel_idx;
+        self.last_num_channels = num_channels;
+        self.last_mode = self.params.mode.value();
+
+        self.sample_rate = buffer_config.sample_rate;
+        self.meter = LoudnessMeter::new(self.sample_rate, num_channels);
+        self.readout = Arc::new(LoudnessReadout::default());
+        self.stop_network();
+        self.stop_hardware();
+
+        // Report our delay up front so the host can apply PDC from the very
+        // first block instead of catching up after the fact. Only Return
+        // instances actually delay audio (via read_pos trailing write_pos by
+        // active_delay) — a Send reports zero, or the host would apply PDC
+        // for latency that was never really there.
+        self.active_delay = clamp_delay(
+            self.params.delay_samples.value() as u32,
+            buffer_config.max_buffer_size as usize,
+        );
+        context.set_latency_samples(reported_latency(self.last_mode, self.active_delay));
+
         true
     }
 
@@ -164,15 +513,13 @@ impl Plugin for EasySend {
         &mut self,
         buffer: &mut Buffer,
         _aux: &mut AuxiliaryBuffers,
-        _context: &mut impl ProcessContext<Self>,
+        context: &mut impl ProcessContext<Self>,
     ) -> ProcessStatus {
         let channel_idx = (self.params.channel.value() as usize).min(MAX_CHANNELS - 1);
         let amount = self.params.amount.value();
         let mode = self.params.mode.value();
         let output_mode = self.params.output.value();
 
-        let rings_store = global_channel_rings();
-
         // metadata
         let num_physical = buffer.channels();
         if num_physical == 0 {
@@ -188,45 +535,161 @@ impl Plugin for EasySend {
             self.last_num_channels = num_physical;
         }
 
-        // Get or create the ChannelRing for this (channel_idx, num_physical) combination
-        let ch_ring = {
-            let mut store = rings_store.lock();
-            let key = (channel_idx, num_physical);
-            
-            if let Some(existing) = store.get(&key) {
-                existing.clone()
+        // Mode is automatable, so a running instance can flip between Send
+        // and Return without a fresh initialize() call — re-report latency
+        // whenever that happens, not just when the delay value itself changes.
+        if mode != self.last_mode {
+            self.last_mode = mode;
+            context.set_latency_samples(reported_latency(mode, self.active_delay));
+        }
+
+        if output_mode != OutputMode::Network && self.network_started {
+            self.stop_network();
+        } else if output_mode == OutputMode::Network && (!self.network_started || channel_changed) {
+            if self.network_started {
+                self.stop_network();
+            }
+            let addr = self.params.network_addr.read().unwrap().clone();
+            let key = if self.params.network_encrypt.value() {
+                let key = self.params.network_key.read().unwrap().clone();
+                (!key.is_empty()).then_some(key)
             } else {
-                // create one ChannelRing with num_physical rings
-                let mut vec_rings = Vec::with_capacity(num_physical);
-                for _ in 0..num_physical {
-                    vec_rings.push(Ring::new(RING_CAP_POW2));
+                None
+            };
+            match mode {
+                Mode::Send => {
+                    let queue = Arc::new(OutboundQueue::default());
+                    self.outbound_queue = Some(queue.clone());
+                    context.execute_background(PluginTask::StartSender { queue, addr, key });
+                }
+                Mode::Return => {
+                    let stop = Arc::new(AtomicBool::new(false));
+                    self.network_stop = Some(stop.clone());
+                    context.execute_background(PluginTask::StartReceiver { stop, addr, key });
                 }
-                let cr = Arc::new(ChannelRing {
-                    write_pos: AtomicUsize::new(0),
-                    rings: vec_rings,
-                });
-                store.insert(key, cr.clone());
-                cr
             }
+            self.network_started = true;
+        }
+
+        if output_mode != OutputMode::HardwareOutput && self.hardware_started {
+            self.stop_hardware();
+        } else if mode == Mode::Send
+            && output_mode == OutputMode::HardwareOutput
+            && (!self.hardware_started || channel_changed)
+        {
+            if self.hardware_started {
+                self.stop_hardware();
+            }
+            let device_name = self.params.hardware_device.read().unwrap().clone();
+            let period = self.params.hardware_period.value() as u32;
+            let stop = Arc::new(AtomicBool::new(false));
+            self.hardware_stop = Some(stop.clone());
+            context.execute_background(PluginTask::StartHardwareOutput {
+                channel_idx,
+                num_physical,
+                device_name,
+                period,
+                sample_rate: self.sample_rate,
+                stop,
+            });
+            self.hardware_started = true;
+        }
+
+        // Hot path: reuse the ring resolved in `initialize` (or the last time
+        // the channel/channel-count changed). `channel_ring_for` allocates on
+        // a registry miss, so it must never be called from here — an
+        // automation move to a (channel, num_physical) combination no one's
+        // touched yet is a normal runtime event, not just something that can
+        // happen at `initialize`. Instead, peek (non-allocating); on a miss,
+        // kick the allocation off to a background task and hold silence
+        // until a later block's peek finds it registered.
+        if channel_changed {
+            self.ring_warming = false;
+        }
+        if channel_changed || self.cached_ring.is_none() {
+            self.cached_ring = peek_channel_ring(channel_idx, num_physical);
+        }
+        let Some(ch_ring) = self.cached_ring.clone() else {
+            if !self.ring_warming {
+                self.ring_warming = true;
+                context.execute_background(PluginTask::WarmRing { channel_idx, num_physical });
+            }
+            for slice in buffer.as_slice().iter_mut() {
+                for s in slice.iter_mut() {
+                    *s = 0.0;
+                }
+            }
+            return ProcessStatus::Normal;
         };
+        self.ring_warming = false;
 
         // now it's safe to get &mut slices
         let slices = buffer.as_slice(); // &mut [ChannelSamples]
 
         match mode {
             Mode::Send => {
-                // IMPORTANT: do fetch_add ONCE, with step = frame_count (frames).
-                // base_frame is the position (in frames) where this block should start.
-                let base_frame = ch_ring.write_pos.fetch_add(frame_count, Ordering::AcqRel);
-
-                // For each physical track write samples into its ring at indices base_frame + i
+                // Several Send instances can target the same channel in the same
+                // host block, and for the bus to actually sum (rather than land at
+                // unrelated offsets) they all need to agree on `base_frame` without
+                // talking to each other. The host's transport sample position is
+                // that shared clock: every instance processing "the same block"
+                // sees the same `pos_samples`, so we use it instead of a private
+                // fetch_add. Hosts that don't report a position fall back to a
+                // private monotonic counter (correct for a lone sender, merely
+                // un-aligned if another sender joins mid-stream).
+                //
+                // Claiming the range is separate from publishing it: `write_pos`
+                // must not advance until every sample below has actually been
+                // summed into the ring, or a concurrently-running Return (there's
+                // no host-enforced ordering between unrelated Send/Return
+                // instances) could see write_pos already covering this range and
+                // read a mix of fresh sums and stale audio from the ring's
+                // previous lap.
+                let base_frame = match context.transport().pos_samples() {
+                    Some(pos) => pos.max(0) as usize,
+                    None => ch_ring.claim_pos.fetch_add(frame_count, Ordering::AcqRel),
+                };
+
+                // The bus is additive: every Send instance targeting this channel
+                // is a contributor to the mix, same as multiple tracks feeding one
+                // aux bus on a real console. `add_at` sums rather than clobbers, so
+                // N sends land in the same frame range without stepping on each other.
                 for (phys_idx, slice) in slices.iter().enumerate() {
                     let ring = &ch_ring.rings[phys_idx];
                     for (i, &s) in slice.iter().enumerate() {
                         let idx = base_frame + i;
                         // Amount is applied here — this is the send level
-                        ring.store_at(idx, s * amount);
+                        ring.add_at(idx, s * amount);
+                    }
+                }
+
+                // Only now is it safe to let readers see this range: every
+                // contribution up to base_frame + frame_count has landed.
+                // fetch_max (not store) keeps write_pos correct even if another
+                // instance's block for a different, larger range publishes first.
+                ch_ring.write_pos.fetch_max(base_frame + frame_count, Ordering::AcqRel);
+
+                // Network — hand this block to the background sender thread
+                // as well, so a remote machine's Return hears the same bus.
+                if let Some(queue) = &self.outbound_queue {
+                    // Reuse a buffer the sender thread already drained and
+                    // handed back, so the steady-state case doesn't allocate
+                    // on the audio thread at all; only the cold-start blocks
+                    // (or a sender thread that's falling behind) fall back to
+                    // `Vec::new()`, which doesn't allocate until first push.
+                    let mut samples = queue.reclaim().unwrap_or_default();
+                    samples.clear();
+                    for i in 0..frame_count {
+                        for slice in slices.iter() {
+                            samples.push(slice[i] * amount);
+                        }
                     }
+                    queue.push(network::Packet {
+                        channel: channel_idx as u8,
+                        num_physical: num_physical as u8,
+                        base_frame: base_frame as u64,
+                        samples,
+                    });
                 }
 
                 // Redirect — zero outputs, PassThrough — leave as is
@@ -243,11 +706,18 @@ impl Plugin for EasySend {
                 // read current write_pos (in frames)
                 let write_pos = ch_ring.write_pos.load(Ordering::Acquire);
 
+                let wanted_delay = clamp_delay(self.params.delay_samples.value() as u32, frame_count);
+                if wanted_delay != self.active_delay {
+                    self.active_delay = wanted_delay;
+                    self.read_initialized = false;
+                    context.set_latency_samples(reported_latency(mode, self.active_delay));
+                }
+
                 if !self.read_initialized {
-                    // Reset read position to be DESIRED_DELAY_SAMPLES behind current write position
+                    // Reset read position to be active_delay behind current write position,
                     // but ensure we don't go negative
-                    if write_pos >= DESIRED_DELAY_SAMPLES {
-                        self.read_pos = write_pos - DESIRED_DELAY_SAMPLES;
+                    if write_pos >= self.active_delay {
+                        self.read_pos = write_pos - self.active_delay;
                         self.read_initialized = true;
                     } else {
                         // not enough data yet — output silence
@@ -286,10 +756,27 @@ impl Plugin for EasySend {
             }
         }
 
+        // Meter whatever this instance actually puts out — the dry/wet send
+        // signal, or the mix a Return is handing back — so LUFS/peak readouts
+        // reflect what the editor would show the user.
+        if self.meter.num_channels() != num_physical {
+            self.meter = LoudnessMeter::new(self.sample_rate, num_physical);
+        }
+        self.frame_scratch.resize(num_physical, 0.0);
+        for i in 0..frame_count {
+            for (phys_idx, slice) in slices.iter().enumerate() {
+                self.frame_scratch[phys_idx] = slice[i];
+            }
+            self.meter.process_frame(&self.frame_scratch, &self.readout);
+        }
+
         ProcessStatus::Normal
     }
 
-    fn deactivate(&mut self) {}
+    fn deactivate(&mut self) {
+        self.stop_network();
+        self.stop_hardware();
+    }
 }
 
 impl ClapPlugin for EasySend {
@@ -0,0 +1,405 @@
+//! EBU R128 style loudness metering (integrated / momentary / short-term LUFS)
+//! plus sample-peak and true-peak, so a `Return` can visualize what it's
+//! returning. Lives alongside the ring machinery: the meter itself is plain
+//! per-instance state updated from `process`, while [`LoudnessReadout`] is the
+//! bit-packed-atomic half that an editor/host can poll from another thread.
+
+use std::collections::VecDeque;
+use std::f64::consts::PI;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// Absolute gate from BS.1770: 400 ms blocks quieter than this are discarded
+/// before the integrated-loudness mean is taken.
+const ABSOLUTE_GATE_LUFS: f64 = -70.0;
+/// Relative gate: after the absolute-gated mean is known, blocks more than
+/// this many LU below it are discarded too, and the mean is recomputed.
+const RELATIVE_GATE_LU: f64 = 10.0;
+
+const SUBBLOCK_MS: f64 = 100.0;
+const MOMENTARY_SUBBLOCKS: usize = 4; // 400 ms
+const SHORT_TERM_SUBBLOCKS: usize = 30; // 3 s
+/// Gating blocks land one per subblock (~100 ms), so this caps integrated
+/// loudness history at roughly an hour of program before the oldest blocks
+/// start rolling off — long enough for any reasonable session, short enough
+/// that the audio thread never carries unbounded state.
+const GATING_HISTORY_CAP: usize = 36_000;
+
+/// One stage of the K-weighting cascade (RBJ cookbook biquad, Direct Form I).
+#[derive(Clone, Copy, Default)]
+struct Biquad {
+    b0: f64,
+    b1: f64,
+    b2: f64,
+    a1: f64,
+    a2: f64,
+    x1: f64,
+    x2: f64,
+    y1: f64,
+    y2: f64,
+}
+
+impl Biquad {
+    /// Stage 1 of K-weighting: a high-shelf boost of `gain_db` above `f0`.
+    fn high_shelf(f0: f64, gain_db: f64, shelf_slope: f64, fs: f64) -> Self {
+        let a = 10f64.powf(gain_db / 40.0);
+        let w0 = 2.0 * PI * f0 / fs;
+        let cos_w0 = w0.cos();
+        let alpha = w0.sin() / 2.0 * ((a + 1.0 / a) * (1.0 / shelf_slope - 1.0) + 2.0).sqrt();
+        let sqrt_a = a.sqrt();
+
+        let b0 = a * ((a + 1.0) + (a - 1.0) * cos_w0 + 2.0 * sqrt_a * alpha);
+        let b1 = -2.0 * a * ((a - 1.0) + (a + 1.0) * cos_w0);
+        let b2 = a * ((a + 1.0) + (a - 1.0) * cos_w0 - 2.0 * sqrt_a * alpha);
+        let a0 = (a + 1.0) - (a - 1.0) * cos_w0 + 2.0 * sqrt_a * alpha;
+        let a1 = 2.0 * ((a - 1.0) - (a + 1.0) * cos_w0);
+        let a2 = (a + 1.0) - (a - 1.0) * cos_w0 - 2.0 * sqrt_a * alpha;
+
+        Self {
+            b0: b0 / a0,
+            b1: b1 / a0,
+            b2: b2 / a0,
+            a1: a1 / a0,
+            a2: a2 / a0,
+            ..Default::default()
+        }
+    }
+
+    /// Stage 2 of K-weighting: the RLB high-pass around `f0`.
+    fn highpass(f0: f64, q: f64, fs: f64) -> Self {
+        let w0 = 2.0 * PI * f0 / fs;
+        let cos_w0 = w0.cos();
+        let alpha = w0.sin() / (2.0 * q);
+
+        let b0 = (1.0 + cos_w0) / 2.0;
+        let b1 = -(1.0 + cos_w0);
+        let b2 = (1.0 + cos_w0) / 2.0;
+        let a0 = 1.0 + alpha;
+        let a1 = -2.0 * cos_w0;
+        let a2 = 1.0 - alpha;
+
+        Self {
+            b0: b0 / a0,
+            b1: b1 / a0,
+            b2: b2 / a0,
+            a1: a1 / a0,
+            a2: a2 / a0,
+            ..Default::default()
+        }
+    }
+
+    #[inline]
+    fn process(&mut self, x: f64) -> f64 {
+        let y = self.b0 * x + self.b1 * self.x1 + self.b2 * self.x2
+            - self.a1 * self.y1
+            - self.a2 * self.y2;
+        self.x2 = self.x1;
+        self.x1 = x;
+        self.y2 = self.y1;
+        self.y1 = y;
+        y
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+struct KWeighting {
+    shelf: Biquad,
+    rlb: Biquad,
+}
+
+impl KWeighting {
+    fn new(fs: f64) -> Self {
+        Self {
+            shelf: Biquad::high_shelf(1500.0, 4.0, 1.0, fs),
+            rlb: Biquad::highpass(38.0, 0.5, fs),
+        }
+    }
+
+    #[inline]
+    fn process(&mut self, x: f64) -> f64 {
+        self.rlb.process(self.shelf.process(x))
+    }
+}
+
+/// A small windowed-sinc upsample-by-4 filter, used only to estimate
+/// true peak: zero-stuff to 4x rate then low-pass so the reconstructed
+/// waveform's peak between samples is visible, same idea as a polyphase
+/// oversampling FIR.
+struct TruePeakOversampler {
+    taps: Vec<f64>,
+    history: Vec<f64>,
+}
+
+const OVERSAMPLE_FACTOR: usize = 4;
+const FIR_HALF_TAPS: usize = 8; // 17-tap windowed sinc per phase
+
+impl TruePeakOversampler {
+    fn new() -> Self {
+        let cutoff = 1.0 / OVERSAMPLE_FACTOR as f64; // normalized to the oversampled rate
+        let n_taps = FIR_HALF_TAPS * 2 * OVERSAMPLE_FACTOR + 1;
+        let center = (n_taps - 1) as f64 / 2.0;
+        let mut taps = Vec::with_capacity(n_taps);
+        for i in 0..n_taps {
+            let n = i as f64 - center;
+            let sinc = if n == 0.0 {
+                cutoff
+            } else {
+                (PI * cutoff * n).sin() / (PI * n)
+            };
+            // Hann window to tame Gibbs ringing.
+            let window = 0.5 - 0.5 * (2.0 * PI * i as f64 / (n_taps - 1) as f64).cos();
+            // Zero-stuffing leaves only one real sample in every
+            // OVERSAMPLE_FACTOR, so scale by that factor to keep unity gain
+            // per phase (otherwise the "true peak" reads ~1/4 of the signal).
+            taps.push(sinc * window * OVERSAMPLE_FACTOR as f64);
+        }
+        Self {
+            taps,
+            history: vec![0.0; FIR_HALF_TAPS * 2 + 1],
+        }
+    }
+
+    /// Feed one input sample, return the peak absolute value among the
+    /// `OVERSAMPLE_FACTOR` interpolated output samples it produced.
+    fn push_and_peak(&mut self, x: f64) -> f64 {
+        self.history.rotate_left(1);
+        *self.history.last_mut().unwrap() = x;
+
+        let mut peak = 0.0f64;
+        for phase in 0..OVERSAMPLE_FACTOR {
+            let mut acc = 0.0;
+            for (h_idx, &h) in self.history.iter().enumerate() {
+                let tap_idx = phase + h_idx * OVERSAMPLE_FACTOR;
+                if let Some(&t) = self.taps.get(tap_idx) {
+                    acc += h * t;
+                }
+            }
+            peak = peak.max(acc.abs());
+        }
+        peak
+    }
+}
+
+#[inline]
+fn bits_to_f32(a: &AtomicU32) -> f32 {
+    f32::from_bits(a.load(Ordering::Relaxed))
+}
+
+#[inline]
+fn store_f32(a: &AtomicU32, v: f32) {
+    a.store(v.to_bits(), Ordering::Relaxed)
+}
+
+/// The cross-thread-readable half of the meter: whatever `process` last
+/// measured, bit-packed into atomics the editor/host can poll without a lock.
+pub struct LoudnessReadout {
+    momentary_lufs: AtomicU32,
+    short_term_lufs: AtomicU32,
+    integrated_lufs: AtomicU32,
+    sample_peak: AtomicU32,
+    true_peak: AtomicU32,
+}
+
+impl Default for LoudnessReadout {
+    fn default() -> Self {
+        Self {
+            momentary_lufs: AtomicU32::new(f32::NEG_INFINITY.to_bits()),
+            short_term_lufs: AtomicU32::new(f32::NEG_INFINITY.to_bits()),
+            integrated_lufs: AtomicU32::new(f32::NEG_INFINITY.to_bits()),
+            sample_peak: AtomicU32::new(0.0f32.to_bits()),
+            true_peak: AtomicU32::new(0.0f32.to_bits()),
+        }
+    }
+}
+
+impl LoudnessReadout {
+    pub fn momentary_lufs(&self) -> f32 {
+        bits_to_f32(&self.momentary_lufs)
+    }
+
+    pub fn short_term_lufs(&self) -> f32 {
+        bits_to_f32(&self.short_term_lufs)
+    }
+
+    pub fn integrated_lufs(&self) -> f32 {
+        bits_to_f32(&self.integrated_lufs)
+    }
+
+    pub fn sample_peak(&self) -> f32 {
+        bits_to_f32(&self.sample_peak)
+    }
+
+    pub fn true_peak(&self) -> f32 {
+        bits_to_f32(&self.true_peak)
+    }
+}
+
+/// Per-instance, audio-thread-owned loudness meter. Channel gain `G_c` is
+/// 1.0 for every channel we see (L/R); surround/LFE weighting isn't modeled
+/// since `EasySend` is stereo-only today.
+pub struct LoudnessMeter {
+    filters: Vec<KWeighting>,
+    oversamplers: Vec<TruePeakOversampler>,
+    subblock_len: usize,
+    subblock_pos: usize,
+    subblock_sum_sq: Vec<f64>, // per-channel, reset every subblock
+    subblock_history: Vec<f64>, // combined G_c-weighted mean-square per subblock
+    gating_blocks: VecDeque<f64>, // 400 ms block loudness values kept for the integrated calc, capped at GATING_HISTORY_CAP
+    // Running sum/count of `gating_blocks` entries above ABSOLUTE_GATE_LUFS,
+    // maintained incrementally as blocks are pushed/evicted so the absolute
+    // pass of `integrated_loudness` never has to rescan the whole history.
+    abs_gated_sum: f64,
+    abs_gated_count: usize,
+}
+
+impl LoudnessMeter {
+    pub fn new(sample_rate: f32, num_channels: usize) -> Self {
+        let fs = sample_rate as f64;
+        let subblock_len = ((SUBBLOCK_MS / 1000.0) * fs).round().max(1.0) as usize;
+        Self {
+            filters: vec![KWeighting::new(fs); num_channels],
+            oversamplers: (0..num_channels).map(|_| TruePeakOversampler::new()).collect(),
+            subblock_len,
+            subblock_pos: 0,
+            subblock_sum_sq: vec![0.0; num_channels],
+            subblock_history: Vec::new(),
+            gating_blocks: VecDeque::new(),
+            abs_gated_sum: 0.0,
+            abs_gated_count: 0,
+        }
+    }
+
+    /// Number of channels this meter was built for.
+    pub fn num_channels(&self) -> usize {
+        self.filters.len()
+    }
+
+    /// Process one frame (one sample per channel) through the meter.
+    pub fn process_frame(&mut self, frame: &[f32], readout: &LoudnessReadout) {
+        let mut sample_peak = 0.0f32;
+        let mut true_peak = 0.0f32;
+
+        for (ch, &s) in frame.iter().enumerate() {
+            sample_peak = sample_peak.max(s.abs());
+            true_peak = true_peak.max(self.oversamplers[ch].push_and_peak(s as f64) as f32);
+
+            let weighted = self.filters[ch].process(s as f64);
+            self.subblock_sum_sq[ch] += weighted * weighted;
+        }
+
+        if sample_peak > readout.sample_peak() {
+            store_f32(&readout.sample_peak, sample_peak);
+        }
+        if true_peak > readout.true_peak() {
+            store_f32(&readout.true_peak, true_peak);
+        }
+
+        self.subblock_pos += 1;
+        if self.subblock_pos >= self.subblock_len {
+            self.finish_subblock(readout);
+            self.subblock_pos = 0;
+        }
+    }
+
+    fn finish_subblock(&mut self, readout: &LoudnessReadout) {
+        // G_c == 1.0 for every channel we track (L/R only).
+        let combined_mean_sq: f64 = self
+            .subblock_sum_sq
+            .iter()
+            .map(|sum| sum / self.subblock_len as f64)
+            .sum();
+        for sum in self.subblock_sum_sq.iter_mut() {
+            *sum = 0.0;
+        }
+
+        self.subblock_history.push(combined_mean_sq);
+        if self.subblock_history.len() > SHORT_TERM_SUBBLOCKS {
+            self.subblock_history.remove(0);
+        }
+
+        let momentary = mean_loudness(&self.subblock_history, MOMENTARY_SUBBLOCKS);
+        let short_term = mean_loudness(&self.subblock_history, SHORT_TERM_SUBBLOCKS);
+        store_f32(&readout.momentary_lufs, momentary as f32);
+        store_f32(&readout.short_term_lufs, short_term as f32);
+
+        // A full 400 ms gating block is available once we have enough history;
+        // its loudness becomes one candidate for the integrated-loudness mean.
+        if self.subblock_history.len() >= MOMENTARY_SUBBLOCKS {
+            let block_mean_sq = mean_of_last(&self.subblock_history, MOMENTARY_SUBBLOCKS);
+            self.push_gating_block(block_mean_sq);
+
+            let integrated = self.integrated_loudness();
+            store_f32(&readout.integrated_lufs, integrated as f32);
+        }
+    }
+
+    /// Appends one gating block and evicts the oldest once `GATING_HISTORY_CAP`
+    /// is exceeded, keeping `abs_gated_sum`/`abs_gated_count` (the absolute
+    /// gate's running mean, whose threshold never moves) in sync incrementally
+    /// instead of rescanning the whole history on every push.
+    fn push_gating_block(&mut self, block_mean_sq: f64) {
+        self.gating_blocks.push_back(block_mean_sq);
+        if loudness_of(block_mean_sq) > ABSOLUTE_GATE_LUFS {
+            self.abs_gated_sum += block_mean_sq;
+            self.abs_gated_count += 1;
+        }
+
+        if self.gating_blocks.len() > GATING_HISTORY_CAP {
+            if let Some(evicted) = self.gating_blocks.pop_front() {
+                if loudness_of(evicted) > ABSOLUTE_GATE_LUFS {
+                    self.abs_gated_sum -= evicted;
+                    self.abs_gated_count -= 1;
+                }
+            }
+        }
+    }
+
+    /// BS.1770 two-pass gating: drop blocks below the absolute gate, take the
+    /// mean of the survivors, drop anything more than `RELATIVE_GATE_LU`
+    /// below that mean, then recompute. The absolute-gated mean is tracked
+    /// incrementally in `abs_gated_sum`/`abs_gated_count`; only the relative
+    /// pass needs a scan, and it sums in place rather than collecting a Vec.
+    fn integrated_loudness(&self) -> f64 {
+        if self.abs_gated_count == 0 {
+            return f64::NEG_INFINITY;
+        }
+        let mean_above_absolute = self.abs_gated_sum / self.abs_gated_count as f64;
+        let relative_gate = loudness_of(mean_above_absolute) - RELATIVE_GATE_LU;
+
+        let mut sum_above_relative = 0.0;
+        let mut count_above_relative = 0usize;
+        for &mean_sq in self.gating_blocks.iter() {
+            if loudness_of(mean_sq) > ABSOLUTE_GATE_LUFS && loudness_of(mean_sq) > relative_gate {
+                sum_above_relative += mean_sq;
+                count_above_relative += 1;
+            }
+        }
+        if count_above_relative == 0 {
+            return f64::NEG_INFINITY;
+        }
+
+        let mean_above_relative = sum_above_relative / count_above_relative as f64;
+        loudness_of(mean_above_relative)
+    }
+}
+
+#[inline]
+fn loudness_of(mean_sq: f64) -> f64 {
+    if mean_sq <= 0.0 {
+        f64::NEG_INFINITY
+    } else {
+        -0.691 + 10.0 * mean_sq.log10()
+    }
+}
+
+fn mean_of_last(history: &[f64], window: usize) -> f64 {
+    let take = window.min(history.len());
+    let slice = &history[history.len() - take..];
+    slice.iter().sum::<f64>() / slice.len() as f64
+}
+
+fn mean_loudness(history: &[f64], window: usize) -> f64 {
+    if history.is_empty() {
+        return f64::NEG_INFINITY;
+    }
+    loudness_of(mean_of_last(history, window))
+}
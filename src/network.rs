@@ -0,0 +1,225 @@
+//! Mirrors a send bus to another machine/process over TCP: a `Send` instance
+//! in `OutputMode::Network` is a client pushing its outgoing blocks out, and a
+//! `Return` instance in `OutputMode::Network` is a server that writes whatever
+//! arrives straight into the local [`crate::ChannelRing`] registry, so other
+//! local `Return`s read networked audio exactly like they'd read local audio.
+
+use std::io::{self, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crossbeam::queue::SegQueue;
+
+/// One block's worth of samples for one channel, framed for the wire.
+pub struct Packet {
+    pub channel: u8,
+    pub num_physical: u8,
+    pub base_frame: u64,
+    /// Interleaved: `num_physical` samples per frame.
+    pub samples: Vec<f32>,
+}
+
+impl Packet {
+    fn encode(&self, key: Option<&str>) -> Vec<u8> {
+        let mut body = Vec::with_capacity(10 + self.samples.len() * 4);
+        body.push(self.channel);
+        body.push(self.num_physical);
+        body.extend_from_slice(&self.base_frame.to_le_bytes());
+        for s in &self.samples {
+            body.extend_from_slice(&s.to_le_bytes());
+        }
+        if let Some(key) = key {
+            xor_keystream(&mut body[10..], key);
+        }
+
+        let mut framed = Vec::with_capacity(4 + body.len());
+        framed.extend_from_slice(&(body.len() as u32).to_le_bytes());
+        framed.extend_from_slice(&body);
+        framed
+    }
+
+    fn decode(mut body: Vec<u8>, key: Option<&str>) -> Option<Self> {
+        if body.len() < 10 {
+            return None;
+        }
+        if let Some(key) = key {
+            xor_keystream(&mut body[10..], key);
+        }
+        let channel = body[0];
+        let num_physical = body[1];
+        let base_frame = u64::from_le_bytes(body[2..10].try_into().ok()?);
+        let samples = body[10..]
+            .chunks_exact(4)
+            .map(|c| f32::from_le_bytes(c.try_into().unwrap()))
+            .collect();
+        Some(Self { channel, num_physical, base_frame, samples })
+    }
+}
+
+/// A small keystream XOR cipher keyed by a shared passphrase. This keeps the
+/// stream off plaintext on a LAN; it is not a vetted cipher and is no
+/// substitute for TLS if that matters for the deployment.
+fn xor_keystream(buf: &mut [u8], passphrase: &str) {
+    let mut state = fnv1a(passphrase.as_bytes());
+    for byte in buf.iter_mut() {
+        state = splitmix64(state);
+        *byte ^= (state & 0xff) as u8;
+    }
+}
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for &b in bytes {
+        hash ^= b as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+fn splitmix64(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9E3779B97F4A7C15);
+    let mut z = x;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z ^ (z >> 31)
+}
+
+fn write_packet(stream: &mut TcpStream, packet: &Packet, key: Option<&str>) -> io::Result<()> {
+    stream.write_all(&packet.encode(key))
+}
+
+fn read_packet(stream: &mut TcpStream, key: Option<&str>) -> io::Result<Option<Packet>> {
+    let mut len_buf = [0u8; 4];
+    if let Err(e) = stream.read_exact(&mut len_buf) {
+        if e.kind() == io::ErrorKind::UnexpectedEof {
+            return Ok(None);
+        }
+        return Err(e);
+    }
+    let len = u32::from_le_bytes(len_buf) as usize;
+    let mut body = vec![0u8; len];
+    stream.read_exact(&mut body)?;
+    Ok(Packet::decode(body, key))
+}
+
+/// The audio thread's side of the handoff: `process` pushes blocks here
+/// without ever touching a socket; the background sender thread drains it.
+/// `SegQueue` is lock-free and allocates only on growth, so pushing never
+/// blocks another instance's audio thread.
+pub struct OutboundQueue {
+    queue: SegQueue<Packet>,
+    /// Drained `Packet::samples` buffers handed back here by the sender
+    /// thread once written, so `process` can reuse their allocation for the
+    /// next block instead of allocating a fresh `Vec` every time.
+    reclaimed: SegQueue<Vec<f32>>,
+    stop: AtomicBool,
+}
+
+impl Default for OutboundQueue {
+    fn default() -> Self {
+        Self { queue: SegQueue::new(), reclaimed: SegQueue::new(), stop: AtomicBool::new(false) }
+    }
+}
+
+impl OutboundQueue {
+    pub fn push(&self, packet: Packet) {
+        self.queue.push(packet);
+    }
+
+    /// Pop a previously drained `samples` buffer for reuse, if one's ready.
+    /// `None` the first few blocks, or if the sender thread is
+    /// falling behind — callers fall back to allocating in that case.
+    pub fn reclaim(&self) -> Option<Vec<f32>> {
+        self.reclaimed.pop()
+    }
+
+    pub fn stop(&self) {
+        self.stop.store(true, Ordering::Release);
+    }
+}
+
+/// Runs on a background task (never the audio thread): connects out to
+/// `addr` and streams whatever `process` enqueues until told to stop.
+pub fn run_sender(queue: Arc<OutboundQueue>, addr: String, key: Option<String>) {
+    let mut stream = match TcpStream::connect(&addr) {
+        Ok(s) => s,
+        Err(_) => return, // best-effort mirror; local send bus still works
+    };
+    let _ = stream.set_nodelay(true);
+
+    while !queue.stop.load(Ordering::Acquire) {
+        match queue.queue.pop() {
+            Some(packet) => {
+                let ok = write_packet(&mut stream, &packet, key.as_deref()).is_ok();
+                let mut samples = packet.samples;
+                samples.clear();
+                queue.reclaimed.push(samples);
+                if !ok {
+                    return;
+                }
+            }
+            None => std::thread::sleep(std::time::Duration::from_millis(1)),
+        }
+    }
+}
+
+/// Runs on a background task: listens for one sender and writes every
+/// incoming frame straight into the matching `ChannelRing`, so local
+/// `Return`s pick it up through the normal read path.
+pub fn run_receiver(bind_addr: String, key: Option<String>, stop: Arc<AtomicBool>) {
+    let listener = match TcpListener::bind(&bind_addr) {
+        Ok(l) => l,
+        Err(_) => return,
+    };
+    let _ = listener.set_nonblocking(true);
+
+    let mut stream = loop {
+        if stop.load(Ordering::Acquire) {
+            return;
+        }
+        match listener.accept() {
+            Ok((s, _)) => break s,
+            Err(_) => std::thread::sleep(std::time::Duration::from_millis(10)),
+        }
+    };
+    let _ = stream.set_nonblocking(false);
+    let _ = stream.set_read_timeout(Some(std::time::Duration::from_millis(200)));
+
+    while !stop.load(Ordering::Acquire) {
+        match read_packet(&mut stream, key.as_deref()) {
+            Ok(Some(packet)) => {
+                let num_physical = packet.num_physical as usize;
+                // The wire is unauthenticated by default, so don't trust
+                // `channel`/`num_physical` enough to size an allocation from
+                // them: a malformed or hostile peer could otherwise drive a
+                // ~100+MB ring allocation per bogus packet (or, since the
+                // registry never evicts, many smaller ones across distinct
+                // channels that add up to the same thing).
+                if packet.channel as usize >= crate::MAX_CHANNELS
+                    || num_physical == 0
+                    || num_physical > crate::MAX_PHYSICAL_CHANNELS
+                {
+                    continue;
+                }
+                let ring = crate::channel_ring_for(packet.channel as usize, num_physical);
+                for (i, frame) in packet.samples.chunks_exact(num_physical).enumerate() {
+                    let idx = packet.base_frame as usize + i;
+                    for (phys_idx, &s) in frame.iter().enumerate() {
+                        // Additive, not a plain overwrite: a local Send may
+                        // already have contributed to this slot, and the two
+                        // need to sum rather than one clobbering the other.
+                        ring.rings[phys_idx].add_at(idx, s);
+                    }
+                }
+                ring.write_pos.fetch_max(
+                    packet.base_frame as usize + packet.samples.len() / num_physical.max(1),
+                    Ordering::AcqRel,
+                );
+            }
+            Ok(None) => return, // remote closed
+            Err(ref e) if e.kind() == io::ErrorKind::WouldBlock || e.kind() == io::ErrorKind::TimedOut => continue,
+            Err(_) => return,
+        }
+    }
+}